@@ -0,0 +1,239 @@
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::{render_prompt, CommitBackend};
+use crate::rate_limiter::RateLimiter;
+
+#[derive(Serialize)]
+struct GenerateContentRequest {
+    contents: Vec<Content>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<Content>,
+    generation_config: GenerationConfig,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Content {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    role: Option<String>,
+    parts: Vec<Part>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Part {
+    text: String,
+}
+
+#[derive(Serialize)]
+struct GenerationConfig {
+    max_output_tokens: u32,
+    temperature: f32,
+}
+
+#[derive(Deserialize)]
+struct GenerateContentResponse {
+    candidates: Vec<Candidate>,
+}
+
+#[derive(Deserialize)]
+struct Candidate {
+    content: Content,
+}
+
+#[derive(Deserialize)]
+struct ListModelsResponse {
+    models: Vec<ModelInfo>,
+}
+
+#[derive(Deserialize)]
+struct ModelInfo {
+    name: String,
+    #[serde(rename = "supportedGenerationMethods")]
+    supported_methods: Option<Vec<String>>,
+}
+
+pub struct GeminiBackend {
+    api_key: String,
+    model: String,
+    prompt_template: String,
+    system_instruction: Option<String>,
+    rate_limiter: Arc<RateLimiter>,
+    client: Client,
+}
+
+impl GeminiBackend {
+    pub fn new(
+        api_key: String,
+        model: String,
+        prompt_template: String,
+        system_instruction: Option<String>,
+        rate_limiter: Arc<RateLimiter>,
+    ) -> Self {
+        Self {
+            api_key,
+            model,
+            prompt_template,
+            system_instruction,
+            rate_limiter,
+            client: Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl CommitBackend for GeminiBackend {
+    async fn list_models(&self) -> Result<Vec<String>> {
+        let url = format!(
+            "https://generativelanguage.googleapis.com/v1beta/models?key={}",
+            self.api_key
+        );
+
+        self.rate_limiter.acquire().await;
+        let res = self.client.get(&url).send().await?;
+
+        if !res.status().is_success() {
+            anyhow::bail!("Falha ao listar modelos: {}", res.status());
+        }
+
+        let list: ListModelsResponse = res.json().await?;
+
+        let mut model_names = Vec::new();
+
+        for model in list.models {
+            if let Some(methods) = model.supported_methods {
+                if methods.contains(&"generateContent".to_string()) {
+                    let clean_name = model.name.replace("models/", "");
+                    model_names.push(clean_name);
+                }
+            }
+        }
+
+        model_names.sort();
+        model_names.reverse();
+
+        if model_names.is_empty() {
+            anyhow::bail!("Nenhum modelo compatível encontrado.");
+        }
+
+        Ok(model_names)
+    }
+
+    async fn generate(&self, diff: &str, language: &str) -> Result<String> {
+        let url = format!(
+            "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+            self.model, self.api_key
+        );
+
+        let body = self.build_request(diff, language);
+
+        self.rate_limiter.acquire().await;
+        let res = self.client.post(&url).json(&body).send().await?;
+
+        if !res.status().is_success() {
+            let err = res.text().await?;
+            anyhow::bail!("Erro da API ({}): {}", self.model, err);
+        }
+
+        let response_json: GenerateContentResponse = res.json().await?;
+
+        let text = response_json
+            .candidates
+            .first()
+            .context("Sem resposta")?
+            .content
+            .parts
+            .first()
+            .context("Sem texto")?
+            .text
+            .clone();
+
+        Ok(text.trim().to_string())
+    }
+
+    async fn generate_stream(
+        &self,
+        diff: &str,
+        language: &str,
+        on_token: &mut (dyn for<'a> FnMut(&'a str) + Send),
+    ) -> Result<String> {
+        let url = format!(
+            "https://generativelanguage.googleapis.com/v1beta/models/{}:streamGenerateContent?alt=sse&key={}",
+            self.model, self.api_key
+        );
+
+        let body = self.build_request(diff, language);
+
+        self.rate_limiter.acquire().await;
+        let res = self.client.post(&url).json(&body).send().await?;
+
+        if !res.status().is_success() {
+            let err = res.text().await?;
+            anyhow::bail!("Erro da API ({}): {}", self.model, err);
+        }
+
+        let mut stream = res.bytes_stream();
+        let mut buffer = String::new();
+        let mut accumulated = String::new();
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+            while let Some(newline_pos) = buffer.find('\n') {
+                let line = buffer[..newline_pos].trim_end_matches('\r').to_string();
+                buffer.drain(..=newline_pos);
+
+                let Some(data) = line.strip_prefix("data: ") else {
+                    continue;
+                };
+
+                if data.is_empty() {
+                    continue;
+                }
+
+                let Ok(fragment) = serde_json::from_str::<GenerateContentResponse>(data) else {
+                    continue;
+                };
+
+                if let Some(text) = fragment
+                    .candidates
+                    .first()
+                    .and_then(|c| c.content.parts.first())
+                {
+                    accumulated.push_str(&text.text);
+                    on_token(&text.text);
+                }
+            }
+        }
+
+        Ok(accumulated.trim().to_string())
+    }
+}
+
+impl GeminiBackend {
+    fn build_request(&self, diff: &str, language: &str) -> GenerateContentRequest {
+        let prompt_text = render_prompt(&self.prompt_template, diff, language);
+
+        GenerateContentRequest {
+            contents: vec![Content {
+                role: None,
+                parts: vec![Part { text: prompt_text }],
+            }],
+            system_instruction: self.system_instruction.as_ref().map(|text| Content {
+                role: Some("system".to_string()),
+                parts: vec![Part {
+                    text: text.clone(),
+                }],
+            }),
+            generation_config: GenerationConfig {
+                max_output_tokens: 1024,
+                temperature: 0.2,
+            },
+        }
+    }
+}
@@ -0,0 +1,107 @@
+mod gemini;
+mod ollama;
+mod openai;
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+pub use gemini::GeminiBackend;
+pub use ollama::OllamaBackend;
+pub use openai::OpenAiBackend;
+
+use crate::config::{AppConfig, ProviderConfig};
+use crate::rate_limiter::RateLimiter;
+
+/// Default prompt used when `AppConfig::prompt_template` is absent.
+pub const DEFAULT_PROMPT_TEMPLATE: &str = "Act as a commit message generator.
+        Analyze the git diff below and generate a SINGLE, complete line of commit message following the Conventional Commits specification (e.g., feat, fix, chore, docs).
+        The message must be concise, objective, and in {language}.
+        Do not truncate the sentence. Do not use quotes or markdown code blocks.
+
+        Diff:
+        {diff}";
+
+/// Prompt used in `--body` mode to ask for a full Conventional Commits
+/// message (header, blank line, wrapped body, optional breaking-change
+/// footer) instead of the usual single-line summary.
+pub const STRUCTURED_PROMPT_TEMPLATE: &str = "Act as a commit message generator.
+        Analyze the git diff below and generate a structured Conventional Commits message in {language}:
+        - First line: \"type(scope): subject\" (type must be one of feat, fix, chore, docs, refactor, test, perf, build, ci, style, revert; scope is optional; add '!' right before the ':' for a breaking change).
+        - A blank line.
+        - A wrapped explanatory body describing what changed and why.
+        - If the diff removes or changes a public API, end with a 'BREAKING CHANGE: ' footer describing the break.
+        Do not use quotes or markdown code blocks.
+
+        Diff:
+        {diff}";
+
+/// Substitutes the `{diff}` and `{language}` placeholders in a user-supplied
+/// (or default) prompt template.
+pub fn render_prompt(template: &str, diff: &str, language: &str) -> String {
+    template
+        .replace("{diff}", diff)
+        .replace("{language}", language)
+}
+
+/// Uniform interface over the LLM providers that can turn a git diff into a
+/// commit message. Each provider speaks its own wire format, but `main` and
+/// the setup wizard only ever talk to this trait.
+#[async_trait]
+pub trait CommitBackend: Send + Sync {
+    async fn list_models(&self) -> Result<Vec<String>>;
+    async fn generate(&self, diff: &str, language: &str) -> Result<String>;
+
+    /// Like `generate`, but invokes `on_token` with each fragment of the
+    /// message as it arrives instead of only returning the final string.
+    /// Backends without a streaming API can fall back to this default,
+    /// which just generates the full message and reports it as one token.
+    async fn generate_stream(
+        &self,
+        diff: &str,
+        language: &str,
+        on_token: &mut (dyn for<'a> FnMut(&'a str) + Send),
+    ) -> Result<String> {
+        let message = self.generate(diff, language).await?;
+        on_token(&message);
+        Ok(message)
+    }
+}
+
+/// Builds the concrete backend described by `cfg`.
+pub fn backend_for(cfg: &AppConfig) -> Box<dyn CommitBackend> {
+    let prompt_template = cfg
+        .prompt_template
+        .clone()
+        .unwrap_or_else(|| DEFAULT_PROMPT_TEMPLATE.to_string());
+    let system_instruction = cfg.system_instruction.clone();
+    let rate_limiter = Arc::new(RateLimiter::new(cfg.max_requests_per_second));
+
+    match &cfg.provider {
+        ProviderConfig::Gemini { api_key, .. } => Box::new(GeminiBackend::new(
+            api_key.clone(),
+            cfg.model.clone(),
+            prompt_template,
+            system_instruction,
+            rate_limiter,
+        )),
+        ProviderConfig::Ollama { endpoint } => Box::new(OllamaBackend::new(
+            endpoint.clone(),
+            cfg.model.clone(),
+            prompt_template,
+            system_instruction,
+            rate_limiter,
+        )),
+        ProviderConfig::OpenAi {
+            api_key, endpoint, ..
+        } => Box::new(OpenAiBackend::new(
+            api_key.clone(),
+            endpoint.clone(),
+            cfg.model.clone(),
+            prompt_template,
+            system_instruction,
+            rate_limiter,
+        )),
+    }
+}
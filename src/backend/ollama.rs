@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::{render_prompt, CommitBackend};
+use crate::rate_limiter::RateLimiter;
+
+#[derive(Serialize)]
+struct GenerateRequest<'a> {
+    model: &'a str,
+    prompt: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
+    stream: bool,
+}
+
+#[derive(Deserialize)]
+struct GenerateResponse {
+    response: String,
+}
+
+#[derive(Deserialize)]
+struct TagsResponse {
+    models: Vec<TagInfo>,
+}
+
+#[derive(Deserialize)]
+struct TagInfo {
+    name: String,
+}
+
+pub struct OllamaBackend {
+    endpoint: String,
+    model: String,
+    prompt_template: String,
+    system_instruction: Option<String>,
+    rate_limiter: Arc<RateLimiter>,
+    client: Client,
+}
+
+impl OllamaBackend {
+    pub fn new(
+        endpoint: String,
+        model: String,
+        prompt_template: String,
+        system_instruction: Option<String>,
+        rate_limiter: Arc<RateLimiter>,
+    ) -> Self {
+        Self {
+            endpoint,
+            model,
+            prompt_template,
+            system_instruction,
+            rate_limiter,
+            client: Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl CommitBackend for OllamaBackend {
+    async fn list_models(&self) -> Result<Vec<String>> {
+        let url = format!("{}/api/tags", self.endpoint.trim_end_matches('/'));
+
+        self.rate_limiter.acquire().await;
+        let res = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("Falha ao conectar ao Ollama. O serviço está rodando?")?;
+
+        if !res.status().is_success() {
+            anyhow::bail!("Falha ao listar modelos: {}", res.status());
+        }
+
+        let list: TagsResponse = res.json().await?;
+
+        let model_names: Vec<String> = list.models.into_iter().map(|m| m.name).collect();
+
+        if model_names.is_empty() {
+            anyhow::bail!("Nenhum modelo encontrado no Ollama. Rode 'ollama pull <modelo>'.");
+        }
+
+        Ok(model_names)
+    }
+
+    async fn generate(&self, diff: &str, language: &str) -> Result<String> {
+        let url = format!("{}/api/generate", self.endpoint.trim_end_matches('/'));
+
+        let prompt = render_prompt(&self.prompt_template, diff, language);
+
+        let body = GenerateRequest {
+            model: &self.model,
+            prompt,
+            system: self.system_instruction.clone(),
+            stream: false,
+        };
+
+        self.rate_limiter.acquire().await;
+        let res = self.client.post(&url).json(&body).send().await?;
+
+        if !res.status().is_success() {
+            let err = res.text().await?;
+            anyhow::bail!("Erro da API ({}): {}", self.model, err);
+        }
+
+        let response_json: GenerateResponse = res.json().await?;
+
+        Ok(response_json.response.trim().to_string())
+    }
+}
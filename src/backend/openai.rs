@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::{render_prompt, CommitBackend};
+use crate::rate_limiter::RateLimiter;
+
+#[derive(Serialize, Deserialize)]
+struct ChatMessage {
+    role: String,
+    content: String,
+}
+
+#[derive(Serialize)]
+struct ChatCompletionRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage>,
+    temperature: f32,
+}
+
+#[derive(Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<Choice>,
+}
+
+#[derive(Deserialize)]
+struct Choice {
+    message: ChatMessage,
+}
+
+#[derive(Deserialize)]
+struct ModelListResponse {
+    data: Vec<ModelInfo>,
+}
+
+#[derive(Deserialize)]
+struct ModelInfo {
+    id: String,
+}
+
+pub struct OpenAiBackend {
+    api_key: String,
+    endpoint: String,
+    model: String,
+    prompt_template: String,
+    system_instruction: Option<String>,
+    rate_limiter: Arc<RateLimiter>,
+    client: Client,
+}
+
+impl OpenAiBackend {
+    pub fn new(
+        api_key: String,
+        endpoint: String,
+        model: String,
+        prompt_template: String,
+        system_instruction: Option<String>,
+        rate_limiter: Arc<RateLimiter>,
+    ) -> Self {
+        Self {
+            api_key,
+            endpoint,
+            model,
+            prompt_template,
+            system_instruction,
+            rate_limiter,
+            client: Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl CommitBackend for OpenAiBackend {
+    async fn list_models(&self) -> Result<Vec<String>> {
+        let url = format!("{}/v1/models", self.endpoint.trim_end_matches('/'));
+
+        self.rate_limiter.acquire().await;
+        let res = self
+            .client
+            .get(&url)
+            .bearer_auth(&self.api_key)
+            .send()
+            .await?;
+
+        if !res.status().is_success() {
+            anyhow::bail!("Falha ao listar modelos: {}", res.status());
+        }
+
+        let list: ModelListResponse = res.json().await?;
+
+        let model_names: Vec<String> = list.data.into_iter().map(|m| m.id).collect();
+
+        if model_names.is_empty() {
+            anyhow::bail!("Nenhum modelo compatível encontrado.");
+        }
+
+        Ok(model_names)
+    }
+
+    async fn generate(&self, diff: &str, language: &str) -> Result<String> {
+        let url = format!(
+            "{}/v1/chat/completions",
+            self.endpoint.trim_end_matches('/')
+        );
+
+        let prompt = render_prompt(&self.prompt_template, diff, language);
+
+        let mut messages = Vec::new();
+        if let Some(system_instruction) = &self.system_instruction {
+            messages.push(ChatMessage {
+                role: "system".to_string(),
+                content: system_instruction.clone(),
+            });
+        }
+        messages.push(ChatMessage {
+            role: "user".to_string(),
+            content: prompt,
+        });
+
+        let body = ChatCompletionRequest {
+            model: &self.model,
+            messages,
+            temperature: 0.2,
+        };
+
+        self.rate_limiter.acquire().await;
+        let res = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.api_key)
+            .json(&body)
+            .send()
+            .await?;
+
+        if !res.status().is_success() {
+            let err = res.text().await?;
+            anyhow::bail!("Erro da API ({}): {}", self.model, err);
+        }
+
+        let response_json: ChatCompletionResponse = res.json().await?;
+
+        let text = response_json
+            .choices
+            .first()
+            .context("Sem resposta")?
+            .message
+            .content
+            .clone();
+
+        Ok(text.trim().to_string())
+    }
+}
@@ -1,63 +1,113 @@
 use anyhow::{Context, Result};
 use dialoguer::{Input, Select, theme::ColorfulTheme};
 use directories::ProjectDirs;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Arc;
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct AppConfig {
-    pub api_key: String,
-    pub model: String,
-}
+use crate::backend::{self, CommitBackend, DEFAULT_PROMPT_TEMPLATE};
+use crate::rate_limiter::RateLimiter;
 
-#[derive(Deserialize)]
-struct ListModelsResponse {
-    models: Vec<ModelInfo>,
+/// Throwaway limiter used only while previewing a provider's model list
+/// during setup; the real limiter is built from the saved config afterwards.
+fn setup_rate_limiter() -> Arc<RateLimiter> {
+    Arc::new(RateLimiter::new(default_max_requests_per_second()))
 }
 
-#[derive(Deserialize)]
-struct ModelInfo {
-    name: String,
-    #[serde(rename = "supportedGenerationMethods")]
-    supported_methods: Option<Vec<String>>,
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "provider")]
+pub enum ProviderConfig {
+    #[serde(rename = "gemini")]
+    Gemini {
+        #[serde(default)]
+        api_key: String,
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        api_key_env_var_name: Option<String>,
+    },
+    #[serde(rename = "ollama")]
+    Ollama { endpoint: String },
+    #[serde(rename = "openai")]
+    OpenAi {
+        #[serde(default)]
+        api_key: String,
+        endpoint: String,
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        api_key_env_var_name: Option<String>,
+    },
 }
 
-async fn get_models(api_key: &str) -> Result<Vec<String>> {
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models?key={}",
-        api_key
-    );
-
-    let client = Client::new();
-    let res = client.get(&url).send().await?;
-
-    if !res.status().is_success() {
-        anyhow::bail!("Falha ao listar modelos: {}", res.status());
-    }
-
-    let list: ListModelsResponse = res.json().await?;
-
-    let mut model_names = Vec::new();
-
-    for model in list.models {
-        if let Some(methods) = model.supported_methods {
-            if methods.contains(&"generateContent".to_string()) {
-                let clean_name = model.name.replace("models/", "");
-                model_names.push(clean_name);
+/// Resolves the effective API key for a provider, preferring the
+/// environment variable named by `api_key_env_var_name` (if set and
+/// present in the environment) over the literal value stored in
+/// `config.toml`. If the variable is configured but unset, we only fall
+/// back to the stored literal when it's non-empty; otherwise we'd silently
+/// call the API with an empty key and the user would see a cryptic 400
+/// instead of knowing which env var to export.
+fn resolve_api_key(api_key: &str, api_key_env_var_name: &Option<String>) -> Result<String> {
+    if let Some(var_name) = api_key_env_var_name {
+        match std::env::var(var_name) {
+            Ok(value) => return Ok(value),
+            Err(_) if api_key.is_empty() => {
+                anyhow::bail!(
+                    "A variável de ambiente '{}' não está definida e nenhuma chave de fallback foi configurada.",
+                    var_name
+                );
             }
+            Err(_) => {}
         }
     }
 
-    model_names.sort();
-    model_names.reverse();
+    Ok(api_key.to_string())
+}
 
-    if model_names.is_empty() {
-        anyhow::bail!("Nenhum modelo compatível encontrado.");
+impl ProviderConfig {
+    fn with_resolved_secrets(self) -> Result<Self> {
+        Ok(match self {
+            ProviderConfig::Gemini {
+                api_key,
+                api_key_env_var_name,
+            } => ProviderConfig::Gemini {
+                api_key: resolve_api_key(&api_key, &api_key_env_var_name)?,
+                api_key_env_var_name,
+            },
+            ProviderConfig::OpenAi {
+                api_key,
+                endpoint,
+                api_key_env_var_name,
+            } => ProviderConfig::OpenAi {
+                api_key: resolve_api_key(&api_key, &api_key_env_var_name)?,
+                endpoint,
+                api_key_env_var_name,
+            },
+            other @ ProviderConfig::Ollama { .. } => other,
+        })
     }
+}
 
-    Ok(model_names)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AppConfig {
+    #[serde(flatten)]
+    pub provider: ProviderConfig,
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub prompt_template: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub system_instruction: Option<String>,
+    #[serde(default = "default_max_requests_per_second")]
+    pub max_requests_per_second: f32,
+    #[serde(default)]
+    pub stream_by_default: bool,
+    #[serde(default = "default_push_after_commit")]
+    pub push_after_commit: bool,
+}
+
+fn default_push_after_commit() -> bool {
+    true
+}
+
+fn default_max_requests_per_second() -> f32 {
+    1.0
 }
 
 fn get_config_path() -> Result<PathBuf> {
@@ -78,8 +128,9 @@ pub async fn load_or_setup() -> Result<AppConfig> {
 
     if config_path.exists() {
         let content = fs::read_to_string(&config_path)?;
-        let config: AppConfig = toml::from_str(&content)
+        let mut config: AppConfig = toml::from_str(&content)
             .context("Arquivo de configuração corrompido. Tente rodar com --reset")?;
+        config.provider = config.provider.with_resolved_secrets()?;
         return Ok(config);
     }
 
@@ -91,22 +142,86 @@ pub async fn run_setup() -> Result<AppConfig> {
     let theme = ColorfulTheme::default();
 
     println!("\nBem-vindo ao AI Commits RS! Vamos configurar.");
-    println!("Obtenha sua chave em: https://aistudio.google.com/app/apikey\n");
 
-    let api_key: String = Input::with_theme(&theme)
-        .with_prompt("Cole sua Google Gemini API Key")
-        .interact_text()?;
+    let providers = vec!["Google Gemini", "Ollama (local)", "OpenAI (ou compatível)"];
+
+    let provider_selection = Select::with_theme(&theme)
+        .with_prompt("Qual provedor de IA você deseja usar?")
+        .default(0)
+        .items(&providers)
+        .interact()?;
+
+    let (provider, models_backend_preview): (ProviderConfig, Box<dyn CommitBackend>) =
+        match provider_selection {
+            0 => {
+                println!("Obtenha sua chave em: https://aistudio.google.com/app/apikey\n");
 
-    let models = match get_models(&api_key).await {
+                let (api_key, api_key_env_var_name) =
+                    prompt_api_key(&theme, "Cole sua Google Gemini API Key")?;
+
+                let provider = ProviderConfig::Gemini {
+                    api_key: api_key.clone(),
+                    api_key_env_var_name,
+                };
+                let backend = backend::GeminiBackend::new(
+                    api_key,
+                    String::new(),
+                    DEFAULT_PROMPT_TEMPLATE.to_string(),
+                    None,
+                    setup_rate_limiter(),
+                );
+                (provider, Box::new(backend))
+            }
+            1 => {
+                let endpoint: String = Input::with_theme(&theme)
+                    .with_prompt("Endereço do Ollama")
+                    .default("http://localhost:11434".to_string())
+                    .interact_text()?;
+
+                let provider = ProviderConfig::Ollama {
+                    endpoint: endpoint.clone(),
+                };
+                let backend = backend::OllamaBackend::new(
+                    endpoint,
+                    String::new(),
+                    DEFAULT_PROMPT_TEMPLATE.to_string(),
+                    None,
+                    setup_rate_limiter(),
+                );
+                (provider, Box::new(backend))
+            }
+            _ => {
+                let endpoint: String = Input::with_theme(&theme)
+                    .with_prompt("Endereço base da API (ex: https://api.openai.com)")
+                    .default("https://api.openai.com".to_string())
+                    .interact_text()?;
+
+                let (api_key, api_key_env_var_name) =
+                    prompt_api_key(&theme, "Cole sua API Key")?;
+
+                let provider = ProviderConfig::OpenAi {
+                    api_key: api_key.clone(),
+                    endpoint: endpoint.clone(),
+                    api_key_env_var_name,
+                };
+                let backend = backend::OpenAiBackend::new(
+                    api_key,
+                    endpoint,
+                    String::new(),
+                    DEFAULT_PROMPT_TEMPLATE.to_string(),
+                    None,
+                    setup_rate_limiter(),
+                );
+                (provider, Box::new(backend))
+            }
+        };
+
+    let models = match models_backend_preview.list_models().await {
         Ok(list) => list,
         Err(e) => {
             println!("Não foi possível listar modelos automaticamente: {}", e);
             println!("Usando lista padrão de fallback.");
-            vec![
-                "gemini-2.0-flash".to_string(),
-                "gemini-1.5-flash".to_string(),
-                "gemini-1.5-pro".to_string(),
-            ]
+            default_models(&provider)
         }
     };
 
@@ -117,14 +232,69 @@ pub async fn run_setup() -> Result<AppConfig> {
         .interact()?;
 
     let config = AppConfig {
-        api_key,
+        provider,
         model: models[selection].to_string(),
+        prompt_template: None,
+        system_instruction: None,
+        max_requests_per_second: default_max_requests_per_second(),
+        stream_by_default: false,
+        push_after_commit: default_push_after_commit(),
     };
 
     save_config(&config)?;
     println!("Configuração salva com sucesso!\n");
 
-    Ok(config)
+    Ok(AppConfig {
+        provider: config.provider.with_resolved_secrets()?,
+        model: config.model,
+        prompt_template: config.prompt_template,
+        system_instruction: config.system_instruction,
+        max_requests_per_second: config.max_requests_per_second,
+        stream_by_default: config.stream_by_default,
+        push_after_commit: config.push_after_commit,
+    })
+}
+
+/// Asks the user how the API key should be supplied: pasted directly into
+/// `config.toml`, or read from an environment variable at runtime. Returns
+/// `(api_key, api_key_env_var_name)`, where `api_key` is empty when the
+/// environment variable route is chosen.
+fn prompt_api_key(theme: &ColorfulTheme, key_prompt: &str) -> Result<(String, Option<String>)> {
+    let options = vec!["Colar a chave diretamente", "Ler de variável de ambiente"];
+
+    let selection = Select::with_theme(theme)
+        .with_prompt("Como deseja fornecer a API key?")
+        .default(0)
+        .items(&options)
+        .interact()?;
+
+    if selection == 0 {
+        let api_key: String = Input::with_theme(theme)
+            .with_prompt(key_prompt)
+            .interact_text()?;
+        return Ok((api_key, None));
+    }
+
+    let var_name: String = Input::with_theme(theme)
+        .with_prompt("Nome da variável de ambiente")
+        .default("GEMINI_API_KEY".to_string())
+        .interact_text()?;
+
+    Ok((String::new(), Some(var_name)))
+}
+
+fn default_models(provider: &ProviderConfig) -> Vec<String> {
+    match provider {
+        ProviderConfig::Gemini { .. } => vec![
+            "gemini-2.0-flash".to_string(),
+            "gemini-1.5-flash".to_string(),
+            "gemini-1.5-pro".to_string(),
+        ],
+        ProviderConfig::Ollama { .. } => vec!["llama3".to_string(), "mistral".to_string()],
+        ProviderConfig::OpenAi { .. } => {
+            vec!["gpt-4o-mini".to_string(), "gpt-4o".to_string()]
+        }
+    }
 }
 
 fn save_config(config: &AppConfig) -> Result<()> {
@@ -0,0 +1,42 @@
+use anyhow::{Context, Result};
+
+/// Commit types accepted by the Conventional Commits specification that
+/// this project enforces on `--body` output.
+const ALLOWED_TYPES: &[&str] = &[
+    "feat", "fix", "chore", "docs", "refactor", "test", "perf", "build", "ci", "style", "revert",
+];
+
+/// Validates that `header` follows `type(scope)!: subject`, with `type` in
+/// [`ALLOWED_TYPES`], an optional parenthesized scope, and an optional `!`
+/// breaking-change marker before the colon.
+pub fn validate_header(header: &str) -> Result<()> {
+    let (prefix, subject) = header
+        .split_once(": ")
+        .context("Cabeçalho não segue o formato 'tipo(escopo): assunto'")?;
+
+    if subject.trim().is_empty() {
+        anyhow::bail!("Cabeçalho sem assunto após o ':'");
+    }
+
+    let prefix = prefix.strip_suffix('!').unwrap_or(prefix);
+
+    let commit_type = match prefix.split_once('(') {
+        Some((commit_type, rest)) => {
+            if !rest.ends_with(')') || rest.len() < 2 {
+                anyhow::bail!("Escopo malformado em '{}'", prefix);
+            }
+            commit_type
+        }
+        None => prefix,
+    };
+
+    if !ALLOWED_TYPES.contains(&commit_type) {
+        anyhow::bail!(
+            "Tipo de commit inválido: '{}'. Use um de: {}",
+            commit_type,
+            ALLOWED_TYPES.join(", ")
+        );
+    }
+
+    Ok(())
+}
@@ -1,11 +1,13 @@
+mod backend;
 mod config;
+mod conventional_commit;
+mod rate_limiter;
 
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
-use dialoguer::{Select, theme::ColorfulTheme};
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
-use std::process::Command;
+use dialoguer::{Editor, Select, theme::ColorfulTheme};
+use std::io::Write;
+use std::process::{Command, Stdio};
 
 #[derive(Parser)]
 #[command(name = "aic")]
@@ -23,43 +25,23 @@ struct Cli {
 
     #[arg(short, long, default_value = "English")]
     language: String,
-}
 
-#[derive(Subcommand)]
-enum Commands {
-    Setup,
-}
+    #[arg(long)]
+    stream: bool,
 
-#[derive(Serialize)]
-struct GenerateContentRequest {
-    contents: Vec<Content>,
-    generation_config: GenerationConfig,
-}
+    #[arg(long)]
+    body: bool,
 
-#[derive(Serialize, Deserialize)]
-struct Content {
-    parts: Vec<Part>,
-}
-
-#[derive(Serialize, Deserialize)]
-struct Part {
-    text: String,
-}
-
-#[derive(Serialize)]
-struct GenerationConfig {
-    max_output_tokens: u32,
-    temperature: f32,
-}
+    #[arg(long)]
+    no_push: bool,
 
-#[derive(Deserialize)]
-struct GenerateContentResponse {
-    candidates: Vec<Candidate>,
+    #[arg(long)]
+    no_verify: bool,
 }
 
-#[derive(Deserialize)]
-struct Candidate {
-    content: Content,
+#[derive(Subcommand)]
+enum Commands {
+    Setup,
 }
 
 fn get_git_diff() -> Result<String> {
@@ -86,58 +68,6 @@ fn get_git_diff() -> Result<String> {
     Ok(diff)
 }
 
-async fn generate_commit(api_key: &str, model: &str, diff: &str, language: &str) -> Result<String> {
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-        model, api_key
-    );
-
-    let prompt_text = format!(
-        "Act as a commit message generator. 
-        Analyze the git diff below and generate a SINGLE, complete line of commit message following the Conventional Commits specification (e.g., feat, fix, chore, docs).
-        The message must be concise, objective, and in {language}.
-        Do not truncate the sentence. Do not use quotes or markdown code blocks.
-        
-        Diff:
-        {diff}",
-        language = language,
-        diff = diff
-    );
-
-    let body = GenerateContentRequest {
-        contents: vec![Content {
-            parts: vec![Part { text: prompt_text }],
-        }],
-        generation_config: GenerationConfig {
-            max_output_tokens: 1024,
-            temperature: 0.2,
-        },
-    };
-
-    let client = Client::new();
-    let res = client.post(&url).json(&body).send().await?;
-
-    if !res.status().is_success() {
-        let err = res.text().await?;
-        anyhow::bail!("Erro da API ({}): {}", model, err);
-    }
-
-    let response_json: GenerateContentResponse = res.json().await?;
-
-    let text = response_json
-        .candidates
-        .first()
-        .context("Sem resposta")?
-        .content
-        .parts
-        .first()
-        .context("Sem texto")?
-        .text
-        .clone();
-
-    Ok(text.trim().to_string())
-}
-
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
@@ -147,7 +77,11 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
-    let cfg = config::load_or_setup().await?;
+    let mut cfg = config::load_or_setup().await?;
+    if cli.body && cfg.prompt_template.is_none() {
+        cfg.prompt_template = Some(backend::STRUCTURED_PROMPT_TEMPLATE.to_string());
+    }
+    let commit_backend = backend::backend_for(&cfg);
 
     if cli.all {
         println!("Adicionando todos os arquivos (git add .)...");
@@ -166,61 +100,137 @@ async fn main() -> Result<()> {
     let diff = get_git_diff()?;
 
     let final_msg: String;
+    let use_stream = cli.stream || cfg.stream_by_default;
+    let should_push = !cli.no_push && cfg.push_after_commit;
+    let mut malformed_attempts = 0u8;
 
-    loop {
+    'regenerate: loop {
         println!("Gerando mensagem de commit com {}...", cfg.model);
-        let msg = generate_commit(&cfg.api_key, &cfg.model, &diff, &cli.language).await?;
+
+        let mut msg = if use_stream {
+            let mut on_token = |token: &str| {
+                print!("{}", token);
+                let _ = std::io::stdout().flush();
+            };
+            let msg = commit_backend
+                .generate_stream(&diff, &cli.language, &mut on_token)
+                .await?;
+            println!();
+            msg
+        } else {
+            commit_backend.generate(&diff, &cli.language).await?
+        };
+
+        if cli.body {
+            let header = msg.lines().next().unwrap_or("");
+            if let Err(e) = conventional_commit::validate_header(header) {
+                malformed_attempts += 1;
+                println!("\nMensagem gerada não segue o Conventional Commits: {}", e);
+
+                if malformed_attempts >= 3 {
+                    anyhow::bail!(
+                        "O modelo não conseguiu gerar um cabeçalho válido após {} tentativas.",
+                        malformed_attempts
+                    );
+                }
+
+                println!("Gerando novamente...\n");
+                continue 'regenerate;
+            }
+        }
 
         if cli.print_only {
-            println!("\n--- Sugestão de Commit Message ---\n");
-            println!("{}", msg);
-            println!("\n----------------------------------");
+            if !use_stream {
+                println!("\n--- Sugestão de Commit Message ---\n");
+                println!("{}", msg);
+                println!("\n----------------------------------");
+            }
             return Ok(());
         }
 
-        println!("\nSugestão: \x1b[1;32m{}\x1b[0m\n", msg);
-
-        let options = vec!["Confirmar (Commit & Push)", "Gerar Novamente", "Cancelar"];
-
-        let selection = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("O que deseja fazer?")
-            .default(0)
-            .items(&options)
-            .interact()?;
-
-        match selection {
-            0 => {
-                final_msg = msg;
-                break;
-            }
-            1 => {
-                println!("Tentando outra opção...\n");
-                continue;
-            }
-            _ => {
-                println!("Operação cancelada pelo usuário.");
-                return Ok(());
+        loop {
+            println!("\nSugestão: \x1b[1;32m{}\x1b[0m\n", msg);
+
+            let confirm_label = if should_push {
+                "Confirmar (Commit & Push)"
+            } else {
+                "Confirmar (Commit)"
+            };
+            let options = vec![confirm_label, "Editar", "Gerar Novamente", "Cancelar"];
+
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("O que deseja fazer?")
+                .default(0)
+                .items(&options)
+                .interact()?;
+
+            match selection {
+                0 => {
+                    final_msg = msg;
+                    break 'regenerate;
+                }
+                1 => {
+                    if let Some(edited) = Editor::new().edit(&msg)? {
+                        msg = edited.trim().to_string();
+                    }
+                    continue;
+                }
+                2 => {
+                    println!("Tentando outra opção...\n");
+                    continue 'regenerate;
+                }
+                _ => {
+                    println!("Operação cancelada pelo usuário.");
+                    return Ok(());
+                }
             }
         }
     }
 
-    let commit_status = Command::new("git")
-        .arg("commit")
-        .arg("-m")
-        .arg(&final_msg)
-        .status()
-        .context("Falha ao executar git commit")?;
+    let mut commit_cmd = Command::new("git");
+    commit_cmd.arg("commit");
+    if cli.no_verify {
+        commit_cmd.arg("--no-verify");
+    }
+
+    let commit_status = if final_msg.contains('\n') {
+        commit_cmd.arg("-F").arg("-").stdin(Stdio::piped());
+        let mut child = commit_cmd.spawn().context("Falha ao executar git commit")?;
+
+        child
+            .stdin
+            .take()
+            .context("Falha ao abrir stdin do git commit")?
+            .write_all(final_msg.as_bytes())
+            .context("Falha ao escrever a mensagem de commit")?;
+
+        child.wait().context("Falha ao executar git commit")?
+    } else {
+        commit_cmd
+            .arg("-m")
+            .arg(&final_msg)
+            .status()
+            .context("Falha ao executar git commit")?
+    };
 
     if !commit_status.success() {
         anyhow::bail!("O git commit falhou. Verifique se há arquivos staged.");
     }
 
+    if !should_push {
+        println!("Commit realizado. Push ignorado (--no-push ou push_after_commit = false).");
+        return Ok(());
+    }
+
     println!("Executando git push...");
 
-    let push_status = Command::new("git")
-        .arg("push")
-        .status()
-        .context("Falha ao executar git push")?;
+    let mut push_cmd = Command::new("git");
+    push_cmd.arg("push");
+    if cli.no_verify {
+        push_cmd.arg("--no-verify");
+    }
+
+    let push_status = push_cmd.status().context("Falha ao executar git push")?;
 
     if push_status.success() {
         println!("Sucesso! Alterações enviadas.");
@@ -0,0 +1,52 @@
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+
+struct State {
+    tokens: f32,
+    last_request: Instant,
+}
+
+/// Token-bucket limiter shared by a backend's outbound API calls. Tokens
+/// refill at `rate` per second (capped at `rate`, i.e. a one-second burst);
+/// `acquire` sleeps just long enough for a token to become available so the
+/// regenerate loop can't outrun a provider's rate limit.
+pub struct RateLimiter {
+    rate: f32,
+    state: Mutex<State>,
+}
+
+impl RateLimiter {
+    pub fn new(rate: f32) -> Self {
+        Self {
+            rate,
+            state: Mutex::new(State {
+                tokens: rate,
+                last_request: Instant::now(),
+            }),
+        }
+    }
+
+    pub async fn acquire(&self) {
+        if self.rate <= 0.0 {
+            // A non-positive rate means "unlimited" rather than "never".
+            return;
+        }
+
+        let mut state = self.state.lock().await;
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.last_request).as_secs_f32();
+        state.last_request = now;
+        state.tokens = (state.tokens + elapsed * self.rate).min(self.rate);
+
+        if state.tokens < 1.0 {
+            let wait = (1.0 - state.tokens) / self.rate;
+            sleep(Duration::from_secs_f32(wait)).await;
+            state.last_request = Instant::now();
+            state.tokens = 0.0;
+        } else {
+            state.tokens -= 1.0;
+        }
+    }
+}